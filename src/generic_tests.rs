@@ -7,12 +7,38 @@
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse2, parse_macro_input, Attribute, Item, ItemFn, ItemMod};
+use syn::{
+    parse2, parse_macro_input, Attribute, AttributeArgs, Item, ItemFn, ItemMod, Meta, MetaList,
+    NestedMeta,
+};
 
-pub fn generic_tests(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn generic_tests(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
     let mut test_mod: ItemMod = parse_macro_input!(input);
     let name = &test_mod.ident;
 
+    // The last path segment of these attributes, in addition to the built-in set, is treated as
+    // test-relevant and moved onto the instantiated function. This lets test-harness attributes
+    // like `#[serial]` or `#[traced_test]` survive monomorphization.
+    let mut extra_attrs = Vec::new();
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::List(MetaList { path, nested, .. }))
+                if path.is_ident("copy_attrs") =>
+            {
+                for item in nested {
+                    match item {
+                        NestedMeta::Meta(Meta::Path(p)) => {
+                            extra_attrs.push(p.segments.last().unwrap().ident.to_string());
+                        }
+                        _ => panic!("copy_attrs arguments must be attribute names"),
+                    }
+                }
+            }
+            _ => panic!("invalid argument {:?}", arg),
+        }
+    }
+
     test_mod.content = test_mod.content.map(|(brace, items)| {
         // TODO A better way of declaring the instantiate macro would be to name it, simply,
         // `instantiate`, and always reference it by qualified name, e.g.
@@ -25,40 +51,9 @@ pub fn generic_tests(_args: TokenStream, input: TokenStream) -> TokenStream {
 
         // Transform each item in the module by removing test attributes. For each test function
         // (function item which has at least one test attribute) append a monomorphized test
-        // function to `macro_body`.
-        let mut items = items
-            .into_iter()
-            .map(|item| {
-                if let Item::Fn(mut f) = item {
-                    let test_attrs = take_test_attrs(&mut f);
-                    if !test_attrs.is_empty() {
-                        let mut test_sig = f.sig.clone();
-                        // The actual test function which gets defined by the macro must not have
-                        // any generics.
-                        test_sig.generics = Default::default();
-                        let test_name = &test_sig.ident;
-                        // The macro will take `$t:ty` as a parameter, so we can use `$t` to invoke
-                        // the generic function with specific type parameters.
-                        let basic_call = quote!(#name::#test_name::<$($t),*>());
-                        // Async test functions require an `await`.
-                        let call = if test_sig.asyncness.is_some() {
-                            quote!(#basic_call.await)
-                        } else {
-                            basic_call
-                        };
-                        macro_body.extend(quote! {
-                            #(#test_attrs)*
-                            #test_sig {
-                                #call
-                            }
-                        });
-                    }
-                    Item::Fn(f)
-                } else {
-                    item
-                }
-            })
-            .collect::<Vec<_>>();
+        // function to `macro_body`. Nested submodules are descended into recursively so their
+        // generic tests are instantiated too.
+        let mut items = take_tests(items, &[], name, &mut macro_body, &extra_attrs);
 
         items.push(
             parse2(quote! {
@@ -82,23 +77,83 @@ pub fn generic_tests(_args: TokenStream, input: TokenStream) -> TokenStream {
     output.into()
 }
 
-fn take_test_attrs(f: &mut ItemFn) -> Vec<Attribute> {
+// Rewrite `items`, stripping test attributes from each test function and appending a monomorphized
+// test function to `macro_body`. `prefix` is the chain of submodule identifiers between the
+// annotated module and the current items, so that the generated call can name the generic function
+// by its fully-qualified path. Submodules are processed recursively.
+fn take_tests(
+    items: Vec<Item>,
+    prefix: &[syn::Ident],
+    name: &syn::Ident,
+    macro_body: &mut proc_macro2::TokenStream,
+    extra: &[String],
+) -> Vec<Item> {
+    items
+        .into_iter()
+        .map(|item| match item {
+            Item::Fn(mut f) => {
+                let test_attrs = take_test_attrs(&mut f, extra);
+                if !test_attrs.is_empty() {
+                    let mut test_sig = f.sig.clone();
+                    // The actual test function which gets defined by the macro must not have any
+                    // generics.
+                    test_sig.generics = Default::default();
+                    let test_name = test_sig.ident.clone();
+                    // The macro will take `$t:ty` as a parameter, so we can use `$t` to invoke the
+                    // generic function with specific type parameters.
+                    let basic_call = quote!(#name #(::#prefix)* ::#test_name::<$($t),*>());
+                    // Async test functions require an `await`.
+                    let call = if test_sig.asyncness.is_some() {
+                        quote!(#basic_call.await)
+                    } else {
+                        basic_call
+                    };
+                    // Qualify the wrapper function's name with its submodule path so that tests
+                    // sharing a short name across sibling sub-groups don't collide into a duplicate
+                    // definition at the instantiation site.
+                    if !prefix.is_empty() {
+                        let joined = prefix
+                            .iter()
+                            .map(|p| p.to_string())
+                            .collect::<Vec<_>>()
+                            .join("_");
+                        test_sig.ident = format_ident!("{}_{}", joined, test_name);
+                    }
+                    macro_body.extend(quote! {
+                        #(#test_attrs)*
+                        #test_sig {
+                            #call
+                        }
+                    });
+                }
+                Item::Fn(f)
+            }
+            Item::Mod(mut m) => {
+                if let Some((brace, sub_items)) = m.content {
+                    let mut sub_prefix = prefix.to_vec();
+                    sub_prefix.push(m.ident.clone());
+                    let sub_items = take_tests(sub_items, &sub_prefix, name, macro_body, extra);
+                    m.content = Some((brace, sub_items));
+                }
+                Item::Mod(m)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+fn take_test_attrs(f: &mut ItemFn, extra: &[String]) -> Vec<Attribute> {
     let (test_attrs, other_attrs) = std::mem::take(&mut f.attrs)
         .into_iter()
-        .partition(is_test_attr);
+        .partition(|attr| is_test_attr(attr, extra));
     f.attrs = other_attrs;
     test_attrs
 }
 
-fn is_test_attr(attr: &Attribute) -> bool {
+fn is_test_attr(attr: &Attribute, extra: &[String]) -> bool {
+    let last = attr.path.segments.last().unwrap().ident.to_string();
     matches!(
-        attr.path
-            .segments
-            .last()
-            .unwrap()
-            .ident
-            .to_string()
-            .as_str(),
+        last.as_str(),
         "test" | "ignore" | "bench" | "should_panic"
-    )
+    ) || extra.contains(&last)
 }
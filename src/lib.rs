@@ -17,6 +17,18 @@ mod generic_tests;
 /// with the test-relevant attributes of the original generic function, whose body simply invokes
 /// the generic function from the module with the given type parameters.
 ///
+/// Test functions defined inside nested submodules of the annotated module are also instantiated.
+/// The module transformation recurses into each submodule, and the wrapper function emitted by the
+/// instantiate macro is named by joining the submodule path with the test name (so `group_a::basic`
+/// and `group_b::basic` instantiate as `group_a_basic` and `group_b_basic` rather than colliding).
+/// Because the instantiate macro invokes each generic function by its fully-qualified path, every
+/// submodule between the annotated module and a generic test must be declared `pub mod`.
+///
+/// By default only the built-in test attributes (`#[test]`, `#[ignore]`, `#[bench]`,
+/// `#[should_panic]`) are treated as test-relevant and moved onto the instantiated functions. Use
+/// `#[generic_tests(copy_attrs(serial, traced_test))]` to extend this set so that attributes from
+/// test-harness crates (e.g. `#[serial]`, `#[traced_test]`) are carried over as well.
+///
 /// Note that, unlike normal test modules, all test functions must be public, since they will be
 /// invoked from wherever the instantiate macro is invoked, which will be outside the module where
 /// the tests are defined.
@@ -145,6 +157,10 @@ mod ser_test;
 ///
 /// # Arguments
 /// * `ark([true|false])` opt in or out of `ark_serialize` tests (the default is `true`)
+/// * `ark(modes(...))` select which ark encodings to round-trip through. Supported modes are
+///   `compressed` (the default), `uncompressed`, and `unchecked`, run through the corresponding
+///   `serialize`/`serialize_uncompressed`/`serialize_unchecked` pairs. The compressed test also
+///   asserts that `serialized_size()` matches the real output length
 /// * `serde([true|false])` opt in or out of `serde` tests (the default is `true`)
 /// * `arbitrary` use the type's `Arbitrary` implementation instead of `Default` to construct a test
 ///   instance
@@ -155,6 +171,21 @@ mod ser_test;
 ///   instance. `f` mut have a signature compatible with `fn f(&mut ChaChaRng) -> Self`
 /// * `constr(f)` use the type's associated function `f` instead of `Default` to construct the test
 ///   instance. `f` must have the signature `fn f() -> Self`
+/// * `cases(N)` run `N` round-trips per type, reseeding the RNG with a distinct deterministic seed
+///   derived from the iteration index each time. Only has an effect for the `arbitrary` and
+///   `random` constructors, since `Default` and `constr` are deterministic. Defaults to 1
+/// * `stable` guard against silent wire-format drift by asserting the serialized bytes of a
+///   fixed-seed instance match a recorded golden. Provide the goldens as hex with
+///   `stable(serde = "..", ark = "..")`; the serde and ark paths get separate checks since their
+///   encodings differ. With no golden for a path the test panics with the observed hex so it can be
+///   pasted back in
+/// * `formats(...)` select which serde codecs to round-trip through. Supported values are
+///   `bincode` (the default), `json`, `cbor`, and `postcard`. A separately-named test is emitted
+///   per format, exposing format-specific serde bugs a bincode-only test would miss
+/// * `all_variants` (enums only) instead of round-tripping a single instance, sample instances
+///   with the `arbitrary`/`random` constructor until every variant has been observed and
+///   round-trip one example of each. Catches serialization bugs affecting a rarely-constructed
+///   variant
 /// * `types(...)` test with the given type parameter list
 #[proc_macro_attribute]
 pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
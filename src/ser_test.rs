@@ -1,7 +1,9 @@
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, AttributeArgs, Ident, Item, Lit, Meta, MetaList, NestedMeta, Type};
+use syn::{
+    parse_macro_input, AttributeArgs, Fields, Ident, Item, Lit, Meta, MetaList, NestedMeta, Type,
+};
 
 pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as AttributeArgs);
@@ -15,7 +17,14 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
     // Parse arguments.
     let mut constr = Constr::Default;
     let mut test_ark = true;
+    let mut ark_modes: Vec<String> = Vec::new();
     let mut test_serde = true;
+    let mut cases = 1u64;
+    let mut formats: Vec<String> = Vec::new();
+    let mut all_variants = false;
+    let mut stable = false;
+    let mut stable_serde_hex: Option<String> = None;
+    let mut stable_ark_hex: Option<String> = None;
     let mut types = Vec::new();
     for arg in args {
         match arg {
@@ -29,6 +38,14 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
                     constr = Constr::Arbitrary;
                 }
 
+                Some(id) if *id == "stable" => {
+                    stable = true;
+                }
+
+                Some(id) if *id == "all_variants" => {
+                    all_variants = true;
+                }
+
                 _ => panic!("invalid argument {:?}", path),
             },
 
@@ -65,14 +82,35 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
                 }
 
                 Some(id) if *id == "ark" => {
-                    if nested.len() != 1 {
-                        panic!("ark attribute takes 1 argument");
-                    }
-                    match &nested[0] {
-                        NestedMeta::Lit(Lit::Bool(b)) => {
-                            test_ark = b.value;
+                    for n in nested.iter() {
+                        match n {
+                            NestedMeta::Lit(Lit::Bool(b)) => {
+                                test_ark = b.value;
+                            }
+                            NestedMeta::Meta(Meta::List(MetaList { path, nested, .. }))
+                                if path.is_ident("modes") =>
+                            {
+                                for m in nested.iter() {
+                                    match m {
+                                        NestedMeta::Meta(Meta::Path(p)) => {
+                                            let mode = p.get_ident().map(|i| i.to_string());
+                                            match mode.as_deref() {
+                                                Some(
+                                                    "compressed" | "uncompressed" | "unchecked",
+                                                ) => ark_modes.push(mode.unwrap()),
+                                                _ => panic!(
+                                                    "unsupported ark mode {:?}; expected \
+                                                     compressed, uncompressed or unchecked",
+                                                    p
+                                                ),
+                                            }
+                                        }
+                                        _ => panic!("ark modes must be mode names"),
+                                    }
+                                }
+                            }
+                            _ => panic!("ark argument must be a boolean or modes(...)"),
                         }
-                        _ => panic!("ark argument must be a boolean"),
                     }
                 }
 
@@ -88,6 +126,60 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
                     }
                 }
 
+                Some(id) if *id == "stable" => {
+                    stable = true;
+                    for n in nested.iter() {
+                        match n {
+                            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                                let key = nv.path.get_ident().map(|i| i.to_string());
+                                let val = match &nv.lit {
+                                    Lit::Str(s) => s.value(),
+                                    _ => panic!("stable values must be hex string literals"),
+                                };
+                                match key.as_deref() {
+                                    Some("serde") => stable_serde_hex = Some(val),
+                                    Some("ark") => stable_ark_hex = Some(val),
+                                    _ => panic!("stable accepts `serde` and `ark` goldens"),
+                                }
+                            }
+                            _ => panic!("stable arguments must be of the form `serde = \"..\"`"),
+                        }
+                    }
+                }
+
+                Some(id) if *id == "cases" => {
+                    if nested.len() != 1 {
+                        panic!("cases attribute takes 1 argument");
+                    }
+                    match &nested[0] {
+                        NestedMeta::Lit(Lit::Int(n)) => {
+                            cases = n.base10_parse().unwrap();
+                        }
+                        _ => panic!("cases argument must be an integer"),
+                    }
+                }
+
+                Some(id) if *id == "formats" => {
+                    for n in nested.iter() {
+                        match n {
+                            NestedMeta::Meta(Meta::Path(p)) => {
+                                let fmt = p.get_ident().map(|i| i.to_string());
+                                match fmt.as_deref() {
+                                    Some("bincode" | "json" | "cbor" | "postcard") => {
+                                        formats.push(fmt.unwrap());
+                                    }
+                                    _ => panic!(
+                                        "unsupported serde format {:?}; \
+                                         expected bincode, json, cbor or postcard",
+                                        p
+                                    ),
+                                }
+                            }
+                            _ => panic!("formats arguments must be format names"),
+                        }
+                    }
+                }
+
                 Some(id) if *id == "types" => {
                     let params = nested.iter().map(parse_type).collect::<Vec<_>>();
                     types.push(quote!(<#name<#(#params),*>>));
@@ -100,6 +192,24 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     }
 
+    // Default to the historical bincode-only behavior when no formats are requested.
+    if formats.is_empty() {
+        formats.push("bincode".to_string());
+    }
+    // Default to the historical compressed-only ark path when no modes are requested.
+    if ark_modes.is_empty() {
+        ark_modes.push("compressed".to_string());
+    }
+
+    // A supplied golden that no selected path would check is a silently-useless wire-format guard,
+    // so reject the combination rather than let the test pass against a bogus golden.
+    if stable_serde_hex.is_some() && !(test_serde && formats.iter().any(|f| f == "bincode")) {
+        panic!("stable(serde = ..) requires the bincode serde format to be tested");
+    }
+    if stable_ark_hex.is_some() && !(test_ark && ark_modes.iter().any(|m| m == "compressed")) {
+        panic!("stable(ark = ..) requires the compressed ark mode to be tested");
+    }
+
     let mut output = quote! {
         #input
     };
@@ -110,14 +220,29 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
         types.push(quote!(<#name>));
     }
 
+    // A golden is a single byte string, but each type parameterization serializes differently, so
+    // one golden cannot be correct for more than one type.
+    if stable && types.len() > 1 {
+        panic!("stable cannot be combined with more than one types(..) parameterization");
+    }
+
+    // The `arbitrary` and `random` constructors draw from a seeded RNG, so we can run many
+    // independent round-trips per type by reseeding with a distinct seed each iteration. The
+    // `Default` and `Method` constructors are deterministic, so looping would only repeat the same
+    // check; in that case we construct a single instance as before.
+    let looped = cases > 1 && matches!(constr, Constr::Random(_) | Constr::Arbitrary);
+
     for (i, ty) in types.into_iter().enumerate() {
-        let constr = match &constr {
+        // Build the constructor expression for an instance, seeding the RNG from `seed`. When
+        // looping, each iteration uses a seed derived from the iteration index so that a failure is
+        // reproducible; otherwise we keep the historical fixed seed.
+        let make_constr = |seed: proc_macro2::TokenStream| match &constr {
             Constr::Default => quote! { #ty::default() },
             Constr::Arbitrary => quote! {
                 {
                     use arbitrary::Unstructured;
                     use rand_chacha::{rand_core::{RngCore, SeedableRng}, ChaChaRng};
-                    let mut rng = ChaChaRng::from_seed([42u8; 32]);
+                    let mut rng = ChaChaRng::from_seed(#seed);
                     let mut data = vec![0u8; 2048];
                     rng.fill_bytes(&mut data);
                     Unstructured::new(&data).arbitrary::#ty().unwrap()
@@ -126,7 +251,7 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
             Constr::Random(f) => quote! {
                 {
                     use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
-                    let mut rng = ChaChaRng::from_seed([42u8; 32]);
+                    let mut rng = ChaChaRng::from_seed(#seed);
                     #ty::#f(&mut rng)
                 }
             },
@@ -135,40 +260,228 @@ pub fn ser_test(args: TokenStream, input: TokenStream) -> TokenStream {
             },
         };
 
-        let serde_test = if test_serde {
+        // When `all_variants` is requested on an enum, sample instances with the (randomized)
+        // constructor until every discriminant has been observed, then round-trip one example of
+        // each. This exercises variants a single `Default`/`random` instance would never reach.
+        if all_variants {
+            let item = match &input {
+                Item::Enum(item) => item,
+                _ => panic!("all_variants can only be applied to an enum"),
+            };
+            if matches!(constr, Constr::Default | Constr::Method(_)) {
+                panic!("all_variants requires the `arbitrary` or `random` constructor");
+            }
+            // The per-variant sampling loop has its own reseeding and constructs a fresh instance
+            // of each variant, so the single-instance `cases`/`stable` machinery does not apply.
+            if cases != 1 {
+                panic!("all_variants cannot be combined with cases(N)");
+            }
+            if stable {
+                panic!("all_variants cannot be combined with stable");
+            }
+
+            let n_variants = item.variants.len();
+            let arms = item.variants.iter().enumerate().map(|(idx, v)| {
+                let vident = &v.ident;
+                let pat = match &v.fields {
+                    Fields::Unit => quote!(#name::#vident),
+                    Fields::Unnamed(_) => quote!(#name::#vident(..)),
+                    Fields::Named(_) => quote!(#name::#vident { .. }),
+                };
+                quote!(#pat => #idx)
+            });
+
+            let constr = make_constr(quote!(__seed));
+            // Generous bound so a rarely-sampled variant still gets found, while a genuinely
+            // unreachable one fails loudly rather than hanging.
+            let max_attempts = (n_variants as u64).max(1) * 10_000;
+
+            let serde_rt = if test_serde {
+                let mut checks = proc_macro2::TokenStream::new();
+                for fmt in &formats {
+                    let rt = serde_round_trip(fmt);
+                    checks.extend(quote!({ #rt }));
+                }
+                checks
+            } else {
+                quote! {}
+            };
+            let ark_rt = if test_ark {
+                let mut checks = proc_macro2::TokenStream::new();
+                for mode in &ark_modes {
+                    let rt = ark_round_trip(mode);
+                    checks.extend(quote!({ use ark_serialize::*; #rt }));
+                }
+                checks
+            } else {
+                quote! {}
+            };
+
             let test_name = Ident::new(
-                &format!("ser_test_serde_round_trip_{}_{}", name, i),
+                &format!("ser_test_all_variants_{}_{}", name, i),
                 Span::mixed_site(),
             );
-            quote! {
+            let variant_test = quote! {
                 #[cfg(test)]
                 #[test]
                 fn #test_name() {
+                    let mut found: Vec<Option<_>> = (0..#n_variants).map(|_| None).collect();
+                    let mut seed = 0u64;
+                    while found.iter().any(Option::is_none) {
+                        assert!(
+                            seed < #max_attempts,
+                            "could not construct every variant of {} by sampling",
+                            stringify!(#name)
+                        );
+                        let mut __seed = [0u8; 32];
+                        __seed[..8].copy_from_slice(&seed.to_le_bytes());
+                        let obj = #constr;
+                        let idx = match &obj {
+                            #(#arms),*
+                        };
+                        if found[idx].is_none() {
+                            found[idx] = Some(obj);
+                        }
+                        seed += 1;
+                    }
+                    for obj in found.into_iter().map(Option::unwrap) {
+                        { #serde_rt }
+                        { #ark_rt }
+                    }
+                }
+            };
+
+            output = quote! {
+                #output
+                #variant_test
+            };
+            continue;
+        }
+
+        // Wrap the per-instance checks `body` (which expect a bound `obj`) in a reseeding loop when
+        // running multiple cases, or emit them once against a fixed-seed instance otherwise.
+        let run = |body: proc_macro2::TokenStream| {
+            if looped {
+                let constr = make_constr(quote!(__case_seed));
+                quote! {
+                    for __case in 0u64..#cases {
+                        let mut __case_seed = [0u8; 32];
+                        __case_seed[..8].copy_from_slice(&__case.to_le_bytes());
+                        let obj = #constr;
+                        #body
+                    }
+                }
+            } else {
+                let constr = make_constr(quote!([42u8; 32]));
+                quote! {
                     let obj = #constr;
-                    let buf = bincode::serialize(&obj).unwrap();
-                    assert_eq!(obj, bincode::deserialize(&buf).unwrap());
+                    #body
                 }
             }
+        };
+
+        let serde_test = if test_serde {
+            let mut serde_tests = proc_macro2::TokenStream::new();
+            for fmt in &formats {
+                let test_name = Ident::new(
+                    &format!("ser_test_serde_round_trip_{}_{}_{}", fmt, name, i),
+                    Span::mixed_site(),
+                );
+                // Each codec has a slightly different API: JSON round-trips through a `String`,
+                // the binary formats through a byte buffer.
+                let round_trip = serde_round_trip(fmt);
+                let body = run(round_trip);
+                // The byte-level golden guard targets the canonical bincode wire format, so it only
+                // runs on the bincode test (see `stable`). It catches format drift a round-trip
+                // cannot, e.g. field reordering or enum discriminant changes.
+                let stable_check = if stable && fmt == "bincode" {
+                    let c = make_constr(quote!([42u8; 32]));
+                    let golden = match &stable_serde_hex {
+                        Some(hex) => quote! {
+                            assert_eq!(
+                                __hex, #hex,
+                                "serde wire format for {} drifted from the recorded golden",
+                                stringify!(#name)
+                            );
+                        },
+                        None => quote! {
+                            panic!(
+                                "no serde golden recorded for {}; observed bincode bytes: \"{}\"",
+                                stringify!(#name), __hex
+                            );
+                        },
+                    };
+                    quote! {
+                        let obj = #c;
+                        let buf = bincode::serialize(&obj).unwrap();
+                        let __hex: String = buf.iter().map(|b| format!("{:02x}", b)).collect();
+                        #golden
+                    }
+                } else {
+                    quote! {}
+                };
+                serde_tests.extend(quote! {
+                    #[cfg(test)]
+                    #[test]
+                    fn #test_name() {
+                        #body
+                        #stable_check
+                    }
+                });
+            }
+            serde_tests
         } else {
             quote! {}
         };
 
         let ark_test = if test_ark {
-            let test_name = Ident::new(
-                &format!("ser_test_ark_serialize_round_trip_{}_{}", name, i),
-                Span::mixed_site(),
-            );
-            quote! {
-                #[cfg(test)]
-                #[test]
-                fn #test_name() {
-                    use ark_serialize::*;
-                    let obj = #constr;
-                    let mut buf = Vec::new();
-                    CanonicalSerialize::serialize(&obj, &mut buf).unwrap();
-                    assert_eq!(obj, CanonicalDeserialize::deserialize(buf.as_slice()).unwrap());
-                }
+            let mut ark_tests = proc_macro2::TokenStream::new();
+            for mode in &ark_modes {
+                let test_name = Ident::new(
+                    &format!("ser_test_ark_serialize_round_trip_{}_{}_{}", mode, name, i),
+                    Span::mixed_site(),
+                );
+                let body = run(ark_round_trip(mode));
+                // The byte-level golden guard and `serialized_size` check both concern the
+                // canonical (compressed) encoding, so they only run on the compressed test.
+                let stable_check = if stable && mode == "compressed" {
+                    let c = make_constr(quote!([42u8; 32]));
+                    let golden = match &stable_ark_hex {
+                        Some(hex) => quote! {
+                            assert_eq!(
+                                __hex, #hex,
+                                "ark_serialize wire format for {} drifted from the recorded golden",
+                                stringify!(#name)
+                            );
+                        },
+                        None => quote! {
+                            panic!(
+                                "no ark golden recorded for {}; observed CanonicalSerialize bytes: \"{}\"",
+                                stringify!(#name), __hex
+                            );
+                        },
+                    };
+                    quote! {
+                        let obj = #c;
+                        let mut buf = Vec::new();
+                        CanonicalSerialize::serialize(&obj, &mut buf).unwrap();
+                        let __hex: String = buf.iter().map(|b| format!("{:02x}", b)).collect();
+                        #golden
+                    }
+                } else {
+                    quote! {}
+                };
+                ark_tests.extend(quote! {
+                    #[cfg(test)]
+                    #[test]
+                    fn #test_name() {
+                        use ark_serialize::*;
+                        #body
+                        #stable_check
+                    }
+                });
             }
+            ark_tests
         } else {
             quote! {}
         };
@@ -190,6 +503,65 @@ enum Constr {
     Method(Ident),
 }
 
+// Emit the serialize/deserialize round-trip check for a single serde codec, assuming a bound `obj`.
+fn serde_round_trip(fmt: &str) -> proc_macro2::TokenStream {
+    match fmt {
+        "bincode" => quote! {
+            let buf = bincode::serialize(&obj).unwrap();
+            assert_eq!(obj, bincode::deserialize(&buf).unwrap());
+        },
+        "json" => quote! {
+            let buf = serde_json::to_string(&obj).unwrap();
+            assert_eq!(obj, serde_json::from_str(&buf).unwrap());
+        },
+        "cbor" => quote! {
+            let buf = serde_cbor::to_vec(&obj).unwrap();
+            assert_eq!(obj, serde_cbor::from_slice(&buf).unwrap());
+        },
+        "postcard" => quote! {
+            let buf = postcard::to_allocvec(&obj).unwrap();
+            assert_eq!(obj, postcard::from_bytes(&buf).unwrap());
+        },
+        _ => unreachable!("unsupported serde format {}", fmt),
+    }
+}
+
+// Emit the CanonicalSerialize/CanonicalDeserialize round-trip for a single ark encoding mode,
+// assuming a bound `obj`. The compressed path additionally asserts that the reported
+// `serialized_size` matches the length actually produced by `serialize`, which catches the common
+// arkworks bug where a hand-written `serialized_size` disagrees with the real output.
+fn ark_round_trip(mode: &str) -> proc_macro2::TokenStream {
+    match mode {
+        "compressed" => quote! {
+            let mut buf = Vec::new();
+            CanonicalSerialize::serialize(&obj, &mut buf).unwrap();
+            assert_eq!(
+                obj.serialized_size(),
+                buf.len(),
+                "serialized_size disagrees with the length of the serialized output"
+            );
+            assert_eq!(obj, CanonicalDeserialize::deserialize(buf.as_slice()).unwrap());
+        },
+        "uncompressed" => quote! {
+            let mut buf = Vec::new();
+            CanonicalSerialize::serialize_uncompressed(&obj, &mut buf).unwrap();
+            assert_eq!(
+                obj,
+                CanonicalDeserialize::deserialize_uncompressed(buf.as_slice()).unwrap()
+            );
+        },
+        "unchecked" => quote! {
+            let mut buf = Vec::new();
+            CanonicalSerialize::serialize_unchecked(&obj, &mut buf).unwrap();
+            assert_eq!(
+                obj,
+                CanonicalDeserialize::deserialize_unchecked(buf.as_slice()).unwrap()
+            );
+        },
+        _ => unreachable!("unsupported ark mode {}", mode),
+    }
+}
+
 fn parse_type(m: &NestedMeta) -> Type {
     match m {
         NestedMeta::Lit(Lit::Str(s)) => syn::parse_str(&s.value()).unwrap(),